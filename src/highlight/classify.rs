@@ -0,0 +1,182 @@
+//! Semantic classification pass.
+//!
+//! Runs over the flat span list the lexer produced and refines some lexical
+//! `TokenKind`s into semantic roles by looking at surrounding tokens: item
+//! names become `Struct`/`Trait`/`Enum`/`Function`, `unsafe` and
+//! `static mut` are flagged distinctly, generic type parameters are told
+//! apart from concrete types, and struct-pattern fields are told apart from
+//! the bindings they're destructured into.
+//!
+//! This is a token-context pass, not a name-resolution pass: it knows
+//! nothing about a binding's declared type, so it can't (for example) tell
+//! a generic `T` used outside its own declaration from a concrete type
+//! named `T`. That's an acceptable gap for a highlighter.
+
+use super::{HighlightSpan, TokenKind};
+
+pub fn classify(source: &str, mut spans: Vec<HighlightSpan>) -> Vec<HighlightSpan> {
+    mark_item_names(source, &mut spans);
+    mark_unsafe_and_static_mut(source, &mut spans);
+    mark_generics(source, &mut spans);
+    mark_struct_pattern_fields(source, &mut spans);
+    spans
+}
+
+fn text<'a>(source: &'a str, span: &HighlightSpan) -> &'a str {
+    &source[span.start..span.end]
+}
+
+/// `struct Foo`, `trait Foo`, `enum Foo`, `fn foo` -> classify `Foo`/`foo`.
+fn mark_item_names(source: &str, spans: &mut [HighlightSpan]) {
+    for i in 1..spans.len() {
+        if spans[i - 1].kind != TokenKind::Keyword {
+            continue;
+        }
+        let keyword = text(source, &spans[i - 1]);
+        let new_kind = match keyword {
+            "struct" => TokenKind::Struct,
+            "trait" => TokenKind::Trait,
+            "enum" => TokenKind::Enum,
+            "fn" => TokenKind::Function,
+            _ => continue,
+        };
+        if matches!(spans[i].kind, TokenKind::Type | TokenKind::Variable) {
+            spans[i].kind = new_kind;
+        }
+    }
+}
+
+/// The `unsafe` keyword itself, and every access to a `static mut NAME`
+/// item: the declaration itself and any later reference to the same name.
+fn mark_unsafe_and_static_mut(source: &str, spans: &mut [HighlightSpan]) {
+    for span in spans.iter_mut() {
+        if span.kind == TokenKind::Keyword && text(source, span) == "unsafe" {
+            span.kind = TokenKind::Unsafe;
+        }
+    }
+
+    // The lexer classifies any identifier starting with an uppercase
+    // letter as `Type` (see `rust::highlight`), and idiomatic statics are
+    // SCREAMING_SNAKE_CASE, so the declared name usually lands here rather
+    // than as `Variable` — accept both.
+    let mut static_mut_names: Vec<&str> = Vec::new();
+    for i in 0..spans.len().saturating_sub(2) {
+        let is_static = spans[i].kind == TokenKind::Keyword && text(source, &spans[i]) == "static";
+        let is_mut = spans[i + 1].kind == TokenKind::Keyword && text(source, &spans[i + 1]) == "mut";
+        let is_name = matches!(spans[i + 2].kind, TokenKind::Variable | TokenKind::Type);
+        if is_static && is_mut && is_name {
+            spans[i + 2].kind = TokenKind::StaticMut;
+            static_mut_names.push(text(source, &spans[i + 2]));
+        }
+    }
+
+    if static_mut_names.is_empty() {
+        return;
+    }
+    for span in spans.iter_mut() {
+        if matches!(span.kind, TokenKind::Variable | TokenKind::Type)
+            && static_mut_names.contains(&text(source, span))
+        {
+            span.kind = TokenKind::StaticMut;
+        }
+    }
+}
+
+/// `Type` tokens inside the `<...>` parameter list right after a
+/// `Function`/`Struct`/`Trait`/`Enum` name become `Generic`.
+fn mark_generics(source: &str, spans: &mut [HighlightSpan]) {
+    let mut i = 0;
+    while i < spans.len() {
+        let is_generic_owner = matches!(
+            spans[i].kind,
+            TokenKind::Function | TokenKind::Struct | TokenKind::Trait | TokenKind::Enum
+        );
+        let opens_generics = is_generic_owner
+            && spans
+                .get(i + 1)
+                .is_some_and(|s| s.kind == TokenKind::Operator && text(source, s) == "<");
+        if !opens_generics {
+            i += 1;
+            continue;
+        }
+        let mut depth = 1;
+        let mut j = i + 2;
+        while j < spans.len() && depth > 0 {
+            let t = text(source, &spans[j]);
+            if spans[j].kind == TokenKind::Operator && t == "<" {
+                depth += 1;
+            } else if spans[j].kind == TokenKind::Operator && t == ">" {
+                depth -= 1;
+            } else if spans[j].kind == TokenKind::Type && depth == 1 {
+                spans[j].kind = TokenKind::Generic;
+            }
+            j += 1;
+        }
+        i = j;
+    }
+}
+
+/// `let Name { x: z, y } = ...` -> `x` and shorthand `y` are `Field`s (`y`
+/// is also the binding, so it stays classified as a field: its rainbow
+/// color, if any, was already assigned by the lexer); the rename target
+/// `z` is a `Binding`, not a field.
+fn mark_struct_pattern_fields(source: &str, spans: &mut [HighlightSpan]) {
+    let mut i = 0;
+    while i < spans.len() {
+        let in_let = spans[i].kind == TokenKind::Keyword && text(source, &spans[i]) == "let";
+        if !in_let {
+            i += 1;
+            continue;
+        }
+        // Expect: let <Struct-ish name> { ... }
+        let Some(name_span) = spans.get(i + 1) else {
+            i += 1;
+            continue;
+        };
+        let is_name = matches!(name_span.kind, TokenKind::Type | TokenKind::Struct);
+        let opens_brace = spans
+            .get(i + 2)
+            .is_some_and(|s| s.kind == TokenKind::Punctuation && text(source, s) == "{");
+        if !(is_name && opens_brace) {
+            i += 1;
+            continue;
+        }
+
+        let mut j = i + 3;
+        let mut expect_field = true;
+        while j < spans.len() {
+            let t = text(source, &spans[j]);
+            if spans[j].kind == TokenKind::Punctuation && t == "}" {
+                j += 1;
+                break;
+            }
+            if spans[j].kind == TokenKind::Operator && t == "," {
+                expect_field = true;
+                j += 1;
+                continue;
+            }
+            if spans[j].kind == TokenKind::Variable && expect_field {
+                let followed_by_colon = spans
+                    .get(j + 1)
+                    .is_some_and(|s| s.kind == TokenKind::Operator && text(source, s) == ":");
+                spans[j].kind = TokenKind::Field;
+                if followed_by_colon {
+                    // Skip the `:`; the next identifier is the rename
+                    // binding, not a field.
+                    if let Some(rename) = spans.get_mut(j + 2) {
+                        if rename.kind == TokenKind::Variable {
+                            rename.kind = TokenKind::Binding;
+                        }
+                    }
+                    j += 3;
+                } else {
+                    j += 1;
+                }
+                expect_field = false;
+                continue;
+            }
+            j += 1;
+        }
+        i = j;
+    }
+}
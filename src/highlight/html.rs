@@ -0,0 +1,127 @@
+//! Self-contained HTML export of a highlighted source file, mirroring
+//! rust-analyzer's HTML snapshot approach. Besides being a way for users to
+//! paste syntax-colored code into blogs/docs, the rendered output is
+//! deterministic and so doubles as a golden-file format for highlighting
+//! tests: render, diff against an expected `.html` file.
+
+use std::fmt::Write as _;
+
+use super::{highlight, Color, HighlightOptions, TokenKind};
+
+/// Render `source` as a self-contained HTML document: a `<pre><code>` block
+/// with one `<span>` per highlighted token, theme colors as embedded CSS
+/// classes, and rainbow hues (when enabled) as inline `style` overrides.
+pub fn highlight_as_html(source: &str, rainbow: bool) -> String {
+    let options = HighlightOptions { rainbow };
+    let spans = highlight(source, &options);
+
+    let mut body = String::new();
+    let mut cursor = 0;
+    for span in &spans {
+        if span.start > cursor {
+            write_escaped(&mut body, &source[cursor..span.start]);
+        }
+        let class = css_class(span.kind);
+        let text = &source[span.start..span.end];
+        match span.color {
+            Some(color) => {
+                let _ = write!(
+                    body,
+                    "<span class=\"{class}\" style=\"color:{}\">",
+                    css_rgb(color)
+                );
+            }
+            None => {
+                let _ = write!(body, "<span class=\"{class}\">");
+            }
+        }
+        write_escaped(&mut body, text);
+        body.push_str("</span>");
+        cursor = span.end;
+    }
+    if cursor < source.len() {
+        write_escaped(&mut body, &source[cursor..]);
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<style>\n{}\n</style>\n</head>\n<body>\n<pre><code>{}</code></pre>\n</body>\n</html>\n",
+        STYLESHEET, body
+    )
+}
+
+fn css_class(kind: TokenKind) -> &'static str {
+    match kind {
+        TokenKind::Keyword => "keyword",
+        TokenKind::Type => "type",
+        TokenKind::String => "string",
+        TokenKind::Number => "number",
+        TokenKind::Comment => "comment",
+        TokenKind::Macro => "macro",
+        TokenKind::Operator => "operator",
+        TokenKind::Lifetime => "lifetime",
+        TokenKind::Variable => "variable",
+        TokenKind::Punctuation => "punctuation",
+        TokenKind::Struct => "struct",
+        TokenKind::Trait => "trait",
+        TokenKind::Enum => "enum",
+        TokenKind::Function => "function",
+        TokenKind::Unsafe => "unsafe",
+        TokenKind::StaticMut => "static-mut",
+        TokenKind::Generic => "generic",
+        TokenKind::Field => "field",
+        TokenKind::Binding => "binding",
+    }
+}
+
+fn css_rgb(color: Color) -> String {
+    format!("rgb({}, {}, {})", color.0, color.1, color.2)
+}
+
+fn write_escaped(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            _ => out.push(c),
+        }
+    }
+}
+
+const STYLESHEET: &str = "\
+pre { background: #1e1e1e; color: #d4d4d4; padding: 1em; }
+.keyword { color: #c586c0; }
+.type { color: #4ec9b0; }
+.string { color: #ce9178; }
+.number { color: #b5cea8; }
+.comment { color: #6a9955; font-style: italic; }
+.macro { color: #dcdcaa; }
+.operator { color: #d4d4d4; }
+.lifetime { color: #569cd6; }
+.variable { color: #9cdcfe; }
+.punctuation { color: #d4d4d4; }
+.struct { color: #4ec9b0; }
+.trait { color: #4ec9b0; font-style: italic; }
+.enum { color: #4ec9b0; }
+.function { color: #dcdcaa; }
+.unsafe { color: #ff6b6b; font-weight: bold; }
+.static-mut { color: #ff6b6b; }
+.generic { color: #4ec9b0; font-style: italic; }
+.field { color: #9cdcfe; }
+.binding { color: #c8c8c8; }";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Golden-file test: render `testdata/sample.rs` and diff it against
+    /// `testdata/sample.html`. If a highlighting change intentionally
+    /// shifts the output, regenerate the fixture and inspect the diff
+    /// before committing it.
+    #[test]
+    fn sample_matches_golden_html() {
+        let source = include_str!("testdata/sample.rs");
+        let expected = include_str!("testdata/sample.html");
+        assert_eq!(highlight_as_html(source, false), expected);
+    }
+}
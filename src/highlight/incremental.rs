@@ -0,0 +1,295 @@
+//! Incremental, per-line re-highlighting for large files.
+//!
+//! Adopts the line-oriented architecture from the `hecto` editor: each line
+//! caches its own highlight spans plus a "continuation" flag recording
+//! whether the line ends inside an unterminated block comment, so editing
+//! one line only requires re-scanning downward until the continuation
+//! state re-converges with what was previously cached — not the whole
+//! file.
+//!
+//! This is intentionally a smaller, line-local scanner rather than a reuse
+//! of [`super::rust::highlight`]: the full lexer tracks brace-scoped
+//! rainbow state across the entire file, which doesn't decompose into
+//! independent per-line chunks. Rainbow coloring is therefore out of scope
+//! here, but keyword/primitive-type classification needs no cross-line
+//! state, so this module still reuses [`super::rust::KEYWORDS`] and
+//! [`super::rust::PRIMITIVE_TYPES`] for those.
+
+use super::rust::{KEYWORDS, PRIMITIVE_TYPES};
+use super::TokenKind;
+
+/// Highlight state carried from the end of one line into the start of the
+/// next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Continuation {
+    #[default]
+    None,
+    /// The line ends inside a `/* ... */` block comment that has not yet
+    /// been closed.
+    BlockComment,
+}
+
+/// A highlighted range within a single line, in byte offsets relative to
+/// that line's start.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineSpan {
+    pub start: usize,
+    pub end: usize,
+    pub kind: TokenKind,
+}
+
+struct LineCache {
+    spans: Vec<LineSpan>,
+    /// `None` means "never scanned" — distinct from `Some(Continuation::None)`,
+    /// which means "scanned and found not to be inside a block comment".
+    /// Collapsing the two let a fresh, unscanned cache entry look like it
+    /// had already converged on the first `rehighlight_from(0)` call,
+    /// stopping the scan before EOF; see `rehighlight_from`.
+    continuation_out: Option<Continuation>,
+}
+
+/// A line-oriented document that keeps highlight results cached per line
+/// and only re-highlights the lines actually affected by an edit.
+pub struct HighlightedDocument {
+    lines: Vec<String>,
+    cache: Vec<LineCache>,
+}
+
+impl HighlightedDocument {
+    pub fn new(source: &str) -> Self {
+        let lines: Vec<String> = source.lines().map(str::to_string).collect();
+        let mut doc = HighlightedDocument {
+            cache: lines
+                .iter()
+                .map(|_| LineCache {
+                    spans: Vec::new(),
+                    continuation_out: None,
+                })
+                .collect(),
+            lines,
+        };
+        doc.rehighlight_from(0);
+        doc
+    }
+
+    pub fn line_spans(&self, line_index: usize) -> &[LineSpan] {
+        &self.cache[line_index].spans
+    }
+
+    /// Replace a line's text in place and re-highlight from it downward.
+    pub fn set_line(&mut self, line_index: usize, text: String) {
+        self.lines[line_index] = text;
+        self.rehighlight_from(line_index);
+    }
+
+    /// Re-highlight starting at `line_index`, continuing downward only as
+    /// long as the recomputed continuation state disagrees with what was
+    /// cached for that line already — once they converge, every line below
+    /// is still valid and scanning stops. A line that was never scanned
+    /// before (`continuation_out: None`) can never look converged, so the
+    /// first pass over a freshly constructed document always reaches EOF.
+    pub fn rehighlight_from(&mut self, line_index: usize) {
+        let mut continuation_in = if line_index == 0 {
+            Continuation::None
+        } else {
+            self.cache[line_index - 1].continuation_out.unwrap_or_default()
+        };
+
+        for i in line_index..self.lines.len() {
+            let previous_out = self.cache[i].continuation_out;
+            let (spans, continuation_out) = scan_line(&self.lines[i], continuation_in);
+            let converged = i > line_index && previous_out == Some(continuation_out);
+            self.cache[i] = LineCache {
+                spans,
+                continuation_out: Some(continuation_out),
+            };
+            if converged {
+                break;
+            }
+            continuation_in = continuation_out;
+        }
+    }
+}
+
+/// Byte offset at which char index `idx` starts, or `line.len()` once `idx`
+/// has run off the end — i.e. the right place to close a span that ended
+/// at `idx`.
+fn offset_at(chars: &[(usize, char)], line: &str, idx: usize) -> usize {
+    chars.get(idx).map(|&(o, _)| o).unwrap_or(line.len())
+}
+
+/// The char index whose char starts at `byte_offset` (which must itself be
+/// a char boundary already found via byte-level string search, e.g. the
+/// end of a `"*/"` match).
+fn char_index_at(chars: &[(usize, char)], byte_offset: usize) -> usize {
+    chars.iter().position(|&(o, _)| o == byte_offset).unwrap_or(chars.len())
+}
+
+/// Scan a single line, starting in `continuation_in` state, returning its
+/// highlight spans and the continuation state carried into the next line.
+///
+/// Walks `line.char_indices()` rather than raw bytes so a multi-byte UTF-8
+/// character can't split a span on a non-char boundary.
+fn scan_line(line: &str, continuation_in: Continuation) -> (Vec<LineSpan>, Continuation) {
+    let chars: Vec<(usize, char)> = line.char_indices().collect();
+    let len = chars.len();
+    let mut spans = Vec::new();
+    let mut i = 0;
+
+    if continuation_in == Continuation::BlockComment {
+        match line.find("*/") {
+            Some(end) => {
+                let end_byte = end + 2;
+                spans.push(LineSpan {
+                    start: 0,
+                    end: end_byte,
+                    kind: TokenKind::Comment,
+                });
+                i = char_index_at(&chars, end_byte);
+            }
+            None => {
+                spans.push(LineSpan {
+                    start: 0,
+                    end: line.len(),
+                    kind: TokenKind::Comment,
+                });
+                return (spans, Continuation::BlockComment);
+            }
+        }
+    }
+
+    while i < len {
+        let (b, c) = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        if c == '/' && chars.get(i + 1).map(|&(_, c)| c) == Some('/') {
+            spans.push(LineSpan {
+                start: b,
+                end: line.len(),
+                kind: TokenKind::Comment,
+            });
+            return (spans, Continuation::None);
+        }
+        if c == '/' && chars.get(i + 1).map(|&(_, c)| c) == Some('*') {
+            match line[b + 2..].find("*/") {
+                Some(rel_end) => {
+                    let end = b + 2 + rel_end + 2;
+                    spans.push(LineSpan {
+                        start: b,
+                        end,
+                        kind: TokenKind::Comment,
+                    });
+                    i = char_index_at(&chars, end);
+                }
+                None => {
+                    spans.push(LineSpan {
+                        start: b,
+                        end: line.len(),
+                        kind: TokenKind::Comment,
+                    });
+                    return (spans, Continuation::BlockComment);
+                }
+            }
+            continue;
+        }
+        if c == '"' {
+            i += 1;
+            while i < len && chars[i].1 != '"' {
+                if chars[i].1 == '\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            i = (i + 1).min(len);
+            spans.push(LineSpan {
+                start: b,
+                end: offset_at(&chars, line, i),
+                kind: TokenKind::String,
+            });
+            continue;
+        }
+        if c.is_ascii_digit() {
+            while i < len && chars[i].1.is_ascii_alphanumeric() {
+                i += 1;
+            }
+            spans.push(LineSpan {
+                start: b,
+                end: offset_at(&chars, line, i),
+                kind: TokenKind::Number,
+            });
+            continue;
+        }
+        if c.is_alphabetic() || c == '_' {
+            while i < len && (chars[i].1.is_alphanumeric() || chars[i].1 == '_') {
+                i += 1;
+            }
+            let end = offset_at(&chars, line, i);
+            let word = &line[b..end];
+            let kind = if KEYWORDS.contains(&word) {
+                TokenKind::Keyword
+            } else if PRIMITIVE_TYPES.contains(&word) {
+                TokenKind::Type
+            } else {
+                TokenKind::Variable
+            };
+            spans.push(LineSpan { start: b, end, kind });
+            continue;
+        }
+        spans.push(LineSpan {
+            start: b,
+            end: b + c.len_utf8(),
+            kind: TokenKind::Punctuation,
+        });
+        i += 1;
+    }
+
+    (spans, Continuation::None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_highlights_every_line_past_a_closed_block_comment() {
+        // A closed `/* ... */` line reports the same `Continuation::None`
+        // as a never-scanned line; if the two are conflated, the very
+        // first construction stops scanning right after it.
+        let doc = HighlightedDocument::new("fn a() {}\n/* doc */\nfn b() {}\nfn c() {}\nfn d() {}\n");
+        for line in 0..5 {
+            assert!(!doc.line_spans(line).is_empty(), "line {line} should have been scanned");
+        }
+    }
+
+    #[test]
+    fn set_line_rehighlights_downward_until_continuation_converges() {
+        let mut doc = HighlightedDocument::new("fn a() {}\n/* one\ntwo */\nfn b() {}\n");
+        assert_eq!(doc.line_spans(1)[0].kind, TokenKind::Comment);
+        assert!(doc.line_spans(3).iter().any(|s| s.kind == TokenKind::Keyword));
+
+        // Closing the comment one line earlier means line 2 now starts
+        // outside it and is re-scanned as code; line 2 still ends outside a
+        // comment, so the scan converges there and line 3 stays as it was.
+        doc.set_line(1, "/* one */".to_string());
+        assert!(doc.line_spans(1).iter().any(|s| s.kind == TokenKind::Comment));
+        assert!(doc.line_spans(2).iter().all(|s| s.kind != TokenKind::Comment));
+        assert!(doc.line_spans(3).iter().any(|s| s.kind == TokenKind::Keyword));
+    }
+
+    #[test]
+    fn unterminated_block_comment_carries_into_the_next_line() {
+        let doc = HighlightedDocument::new("/* open\nstill inside\n*/ fn a() {}\n");
+        assert_eq!(doc.line_spans(0), &[LineSpan { start: 0, end: 7, kind: TokenKind::Comment }]);
+        assert_eq!(
+            doc.line_spans(1),
+            &[LineSpan {
+                start: 0,
+                end: "still inside".len(),
+                kind: TokenKind::Comment
+            }]
+        );
+        assert!(doc.line_spans(2).iter().any(|s| s.kind == TokenKind::Keyword));
+    }
+}
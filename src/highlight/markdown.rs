@@ -0,0 +1,156 @@
+//! Markdown fenced-code detection and nested language highlighting.
+//!
+//! Fenced code blocks (```` ```lang ````) are dispatched to the matching
+//! language highlighter instead of being colored as one uniform string, the
+//! way rust-analyzer highlights Rust inside doc-comment and README code
+//! fences. The info string after the fence is parsed for a language id and
+//! optional `filename="..."` metadata (as Shiki/Nextra accept); a fence with
+//! no language id falls back to selecting by the filename's extension via
+//! [`LanguageRegistry::by_extension`]. Unrecognized languages fall back to
+//! plain, unhighlighted text.
+
+use super::{HighlightOptions, HighlightSpan, LanguageRegistry, TokenKind};
+
+/// Parsed contents of a fence's info string, e.g. `rust filename="main.rs"`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FenceInfo {
+    pub lang: Option<String>,
+    pub filename: Option<String>,
+}
+
+fn parse_fence_info(info: &str) -> FenceInfo {
+    let mut lang = None;
+    let mut filename = None;
+    for part in info.split_whitespace() {
+        if let Some(value) = part.strip_prefix("filename=") {
+            filename = Some(value.trim_matches('"').to_string());
+        } else if lang.is_none() {
+            // The first part that isn't `filename=...` metadata is the
+            // language id, whichever position it appears in (it's usually
+            // first, but a fence can carry only `filename=` metadata and
+            // no language id at all).
+            lang = Some(part.to_string());
+        }
+    }
+    FenceInfo { lang, filename }
+}
+
+fn highlight_fenced_body(fence: &FenceInfo, code: &str, options: &HighlightOptions) -> Vec<HighlightSpan> {
+    let registry = LanguageRegistry::with_builtins();
+    let language = match fence.lang.as_deref() {
+        // "rs" is a common alias for "rust" in fenced-code info strings;
+        // the registry itself is keyed by the canonical id.
+        Some("rs") => registry.by_id("rust"),
+        Some(id) => registry.by_id(id),
+        // No language on the fence itself; fall back to the `filename=`
+        // metadata's extension, the way Shiki/Nextra fences do.
+        None => fence
+            .filename
+            .as_deref()
+            .and_then(|name| name.rsplit('.').next())
+            .and_then(|ext| registry.by_extension(ext)),
+    };
+    match language {
+        // Unrecognized (or absent) language falls back to plain,
+        // unhighlighted text rather than guessing.
+        Some(language) => language.tokenize(code, options),
+        None => Vec::new(),
+    }
+}
+
+/// Highlight a Markdown document: prose is left unhighlighted, and fenced
+/// code blocks are highlighted with their declared language's highlighter.
+pub fn highlight_markdown(source: &str, options: &HighlightOptions) -> Vec<HighlightSpan> {
+    let mut spans = Vec::new();
+    let lines: Vec<&str> = source.split_inclusive('\n').collect();
+    let mut pos = 0;
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        let trimmed = line.trim_end_matches('\n');
+        if let Some(info) = trimmed.trim_start().strip_prefix("```") {
+            let fence_info = parse_fence_info(info.trim());
+            spans.push(HighlightSpan {
+                start: pos,
+                end: pos + trimmed.len(),
+                kind: TokenKind::Punctuation,
+                color: None,
+            });
+            pos += line.len();
+            i += 1;
+
+            let block_start = pos;
+            while i < lines.len() && lines[i].trim_end_matches('\n').trim() != "```" {
+                pos += lines[i].len();
+                i += 1;
+            }
+            let block_end = pos;
+            let code = &source[block_start..block_end];
+            for inner in highlight_fenced_body(&fence_info, code, options) {
+                spans.push(HighlightSpan {
+                    start: inner.start + block_start,
+                    end: inner.end + block_start,
+                    kind: inner.kind,
+                    color: inner.color,
+                });
+            }
+
+            if i < lines.len() {
+                // Closing fence line.
+                let closing = lines[i].trim_end_matches('\n');
+                spans.push(HighlightSpan {
+                    start: pos,
+                    end: pos + closing.len(),
+                    kind: TokenKind::Punctuation,
+                    color: None,
+                });
+                pos += lines[i].len();
+                i += 1;
+            }
+            continue;
+        }
+
+        pos += line.len();
+        i += 1;
+    }
+
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fences_a_rust_block_by_language_id() {
+        let source = "prose\n```rust\nfn f() {}\n```\nmore prose\n";
+        let options = HighlightOptions::default();
+        let spans = highlight_markdown(source, &options);
+        let fn_span = spans
+            .iter()
+            .find(|s| s.kind == TokenKind::Keyword && &source[s.start..s.end] == "fn")
+            .expect("the fenced Rust body was highlighted");
+        assert!(fn_span.start > source.find("```rust").unwrap());
+    }
+
+    #[test]
+    fn falls_back_to_the_filename_extension_when_no_language_id_is_given() {
+        let source = "```filename=\"Cargo.toml\"\nname = \"x\"\n```\n";
+        let options = HighlightOptions::default();
+        let spans = highlight_markdown(source, &options);
+        assert!(
+            spans.iter().any(|s| s.kind == TokenKind::Field && &source[s.start..s.end] == "name"),
+            "TOML key should have been highlighted via the filename's extension"
+        );
+    }
+
+    #[test]
+    fn unknown_language_falls_back_to_plain_text() {
+        let source = "```cobol\nMOVE 1 TO X.\n```\n";
+        let options = HighlightOptions::default();
+        let spans = highlight_markdown(source, &options);
+        // Only the fence delimiters themselves are highlighted.
+        assert_eq!(spans.iter().filter(|s| s.kind != TokenKind::Punctuation).count(), 0);
+    }
+}
@@ -0,0 +1,39 @@
+//! Fixed hue palette used to assign stable, uniquely-derived colors to
+//! local bindings and lifetimes under rainbow highlighting.
+
+/// An RGB color swatch from the rainbow palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+/// Palette of visually distinct hues, cycled by hash. Kept small and
+/// high-contrast so adjacent bindings rarely land on neighboring colors.
+const PALETTE: &[Color] = &[
+    Color(230, 126, 34),  // orange
+    Color(52, 152, 219),  // blue
+    Color(46, 204, 113),  // green
+    Color(231, 76, 60),   // red
+    Color(155, 89, 182),  // purple
+    Color(241, 196, 15),  // yellow
+    Color(26, 188, 156),  // teal
+    Color(233, 30, 99),   // pink
+    Color(121, 85, 72),   // brown
+    Color(0, 188, 212),   // cyan
+    Color(139, 195, 74),  // lime
+    Color(255, 87, 34),   // deep orange
+];
+
+/// Derive a stable palette entry for a binding from its name and the byte
+/// offset of its defining span. Hashing the definition span (not just the
+/// name) means two bindings that happen to share a name in unrelated scopes
+/// hash differently, matching shadowing semantics; with only
+/// `PALETTE.len()` hues, two different hashes can still pick the same one.
+pub fn color_for_binding(name: &str, def_start: usize) -> Color {
+    let mut hash: u64 = 14695981039346656037; // FNV-1a offset basis
+    for byte in name.as_bytes() {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(1099511628211);
+    }
+    hash ^= def_start as u64;
+    hash = hash.wrapping_mul(1099511628211);
+    PALETTE[(hash as usize) % PALETTE.len()]
+}
@@ -0,0 +1,162 @@
+//! Pluggable multi-language grammar registry.
+//!
+//! Generalizes the highlighter from a single hard-coded Rust lexer into a
+//! registry keyed by language id, so new languages can be added without
+//! touching the renderer: implement [`Language`], register it, and the
+//! same `TokenKind` -> theme mapping drives highlighting for it too.
+
+use super::{rust, toml, HighlightOptions, HighlightSpan};
+
+/// A registered grammar: a tokenizer plus the lexical tables that describe
+/// it, so tooling (e.g. a future grammar inspector) can introspect a
+/// language without re-parsing its source.
+pub trait Language {
+    /// Stable identifier, e.g. `"rust"`. Matched case-insensitively by
+    /// [`LanguageRegistry::by_id`], e.g. against fenced-code info strings.
+    fn id(&self) -> &'static str;
+    /// File extensions (without the dot) this language is selected for.
+    fn extensions(&self) -> &'static [&'static str];
+    fn keywords(&self) -> &'static [&'static str];
+    fn type_prefixes(&self) -> &'static [&'static str];
+    fn operators(&self) -> &'static str;
+    /// Tokenize `source`, yielding highlight spans.
+    fn tokenize(&self, source: &str, options: &HighlightOptions) -> Vec<HighlightSpan>;
+}
+
+struct RustLanguage;
+
+impl Language for RustLanguage {
+    fn id(&self) -> &'static str {
+        "rust"
+    }
+    fn extensions(&self) -> &'static [&'static str] {
+        &["rs"]
+    }
+    fn keywords(&self) -> &'static [&'static str] {
+        rust::KEYWORDS
+    }
+    fn type_prefixes(&self) -> &'static [&'static str] {
+        rust::PRIMITIVE_TYPES
+    }
+    fn operators(&self) -> &'static str {
+        rust::OPERATORS
+    }
+    fn tokenize(&self, source: &str, options: &HighlightOptions) -> Vec<HighlightSpan> {
+        rust::highlight(source, options)
+    }
+}
+
+struct TomlLanguage;
+
+impl Language for TomlLanguage {
+    fn id(&self) -> &'static str {
+        "toml"
+    }
+    fn extensions(&self) -> &'static [&'static str] {
+        &["toml"]
+    }
+    fn keywords(&self) -> &'static [&'static str] {
+        toml::KEYWORDS
+    }
+    fn type_prefixes(&self) -> &'static [&'static str] {
+        &[]
+    }
+    fn operators(&self) -> &'static str {
+        toml::OPERATORS
+    }
+    fn tokenize(&self, source: &str, _options: &HighlightOptions) -> Vec<HighlightSpan> {
+        toml::highlight(source)
+    }
+}
+
+/// Holds the set of languages the highlighter knows how to tokenize,
+/// looked up by language id (as used in fenced-code info strings) or file
+/// extension.
+pub struct LanguageRegistry {
+    languages: Vec<Box<dyn Language>>,
+}
+
+impl LanguageRegistry {
+    /// A registry containing every built-in grammar.
+    pub fn with_builtins() -> Self {
+        LanguageRegistry {
+            languages: vec![Box::new(RustLanguage), Box::new(TomlLanguage)],
+        }
+    }
+
+    pub fn register(&mut self, language: Box<dyn Language>) {
+        self.languages.push(language);
+    }
+
+    pub fn by_id(&self, id: &str) -> Option<&dyn Language> {
+        self.languages
+            .iter()
+            .find(|lang| lang.id().eq_ignore_ascii_case(id))
+            .map(|boxed| boxed.as_ref())
+    }
+
+    pub fn by_extension(&self, extension: &str) -> Option<&dyn Language> {
+        self.languages
+            .iter()
+            .find(|lang| lang.extensions().iter().any(|ext| ext.eq_ignore_ascii_case(extension)))
+            .map(|boxed| boxed.as_ref())
+    }
+}
+
+impl Default for LanguageRegistry {
+    fn default() -> Self {
+        Self::with_builtins()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn by_id_is_case_insensitive_and_finds_both_builtins() {
+        let registry = LanguageRegistry::with_builtins();
+        assert_eq!(registry.by_id("rust").unwrap().id(), "rust");
+        assert_eq!(registry.by_id("RUST").unwrap().id(), "rust");
+        assert_eq!(registry.by_id("toml").unwrap().id(), "toml");
+        assert!(registry.by_id("cobol").is_none());
+    }
+
+    #[test]
+    fn by_extension_finds_the_owning_language() {
+        let registry = LanguageRegistry::with_builtins();
+        assert_eq!(registry.by_extension("rs").unwrap().id(), "rust");
+        assert_eq!(registry.by_extension("TOML").unwrap().id(), "toml");
+        assert!(registry.by_extension("py").is_none());
+    }
+
+    #[test]
+    fn register_adds_a_language_findable_by_id_and_extension() {
+        struct FakeLanguage;
+        impl Language for FakeLanguage {
+            fn id(&self) -> &'static str {
+                "fake"
+            }
+            fn extensions(&self) -> &'static [&'static str] {
+                &["fk"]
+            }
+            fn keywords(&self) -> &'static [&'static str] {
+                &[]
+            }
+            fn type_prefixes(&self) -> &'static [&'static str] {
+                &[]
+            }
+            fn operators(&self) -> &'static str {
+                ""
+            }
+            fn tokenize(&self, _source: &str, _options: &HighlightOptions) -> Vec<HighlightSpan> {
+                Vec::new()
+            }
+        }
+
+        let mut registry = LanguageRegistry::with_builtins();
+        registry.register(Box::new(FakeLanguage));
+        assert_eq!(registry.by_id("fake").unwrap().id(), "fake");
+        assert_eq!(registry.by_extension("fk").unwrap().id(), "fake");
+    }
+}
@@ -0,0 +1,379 @@
+//! Minimal Rust tokenizer backing the highlighting subsystem.
+//!
+//! This is a best-effort lexer, not a full parser: it is good enough to
+//! classify tokens for highlighting and to track `let`-binding and closure
+//! parameter scopes for rainbow coloring, but it does not build an AST.
+//! Lifetimes are colored by name alone: every use of the same lifetime name
+//! in a file shares one hue, whichever item declares it.
+//!
+//! Scanning walks `source.char_indices()` rather than raw bytes: indexing
+//! a multi-byte UTF-8 character by byte and re-casting it to `char` (as a
+//! naive `bytes[i] as char` would) misclassifies continuation bytes and
+//! can slice `source` on a non-char boundary. Walking whole chars keeps
+//! every span boundary valid no matter what the source text contains.
+
+use std::collections::HashMap;
+
+use super::palette::{color_for_binding, Color};
+use super::{HighlightOptions, HighlightSpan, TokenKind};
+
+pub(crate) const KEYWORDS: &[&str] = &[
+    "as", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn",
+    "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe",
+    "use", "where", "while", "async", "await",
+];
+
+pub(crate) const PRIMITIVE_TYPES: &[&str] = &[
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize", "f32",
+    "f64", "bool", "char", "str", "String", "Vec", "Option", "Result", "Box",
+];
+
+pub(crate) const OPERATORS: &str = "+-*/%=<>!&|^.,;:()[]";
+
+struct Binding {
+    color: Color,
+}
+
+/// Tracks `let`/closure-param scopes (by brace depth) and lifetime
+/// identities for stable rainbow coloring.
+struct RainbowState {
+    scopes: Vec<HashMap<String, Binding>>,
+    lifetimes: HashMap<String, Color>,
+}
+
+impl RainbowState {
+    fn new() -> Self {
+        RainbowState {
+            scopes: vec![HashMap::new()],
+            lifetimes: HashMap::new(),
+        }
+    }
+
+    fn push_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    fn pop_scope(&mut self) {
+        if self.scopes.len() > 1 {
+            self.scopes.pop();
+        }
+    }
+
+    fn define(&mut self, name: &str, def_start: usize) -> Color {
+        let color = color_for_binding(name, def_start);
+        self.scopes
+            .last_mut()
+            .expect("at least one scope")
+            .insert(name.to_string(), Binding { color });
+        color
+    }
+
+    fn lookup(&self, name: &str) -> Option<Color> {
+        for scope in self.scopes.iter().rev() {
+            if let Some(binding) = scope.get(name) {
+                return Some(binding.color);
+            }
+        }
+        None
+    }
+
+    fn lifetime(&mut self, name: &str, def_start: usize) -> Color {
+        *self
+            .lifetimes
+            .entry(name.to_string())
+            .or_insert_with(|| color_for_binding(name, def_start))
+    }
+}
+
+/// Byte offset at which char index `idx` starts, or `source.len()` once
+/// `idx` has run off the end — i.e. the right place to close a span that
+/// ended at `idx`.
+fn offset_at(chars: &[(usize, char)], source: &str, idx: usize) -> usize {
+    chars.get(idx).map(|&(o, _)| o).unwrap_or(source.len())
+}
+
+pub fn highlight(source: &str, options: &HighlightOptions) -> Vec<HighlightSpan> {
+    let chars: Vec<(usize, char)> = source.char_indices().collect();
+    let len = chars.len();
+    let mut spans = Vec::new();
+    let mut rainbow = RainbowState::new();
+    let mut i = 0;
+    // Set when the previous significant identifier was `let` (a `mut` right
+    // after it keeps it set), so the next identifier is treated as a binding
+    // definition rather than a use.
+    let mut expect_binding = false;
+
+    while i < len {
+        let (b, c) = chars[i];
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        // Line comments.
+        if c == '/' && chars.get(i + 1).map(|&(_, c)| c) == Some('/') {
+            while i < len && chars[i].1 != '\n' {
+                i += 1;
+            }
+            spans.push(span(b, offset_at(&chars, source, i), TokenKind::Comment, None));
+            continue;
+        }
+
+        // Block comments.
+        if c == '/' && chars.get(i + 1).map(|&(_, c)| c) == Some('*') {
+            i += 2;
+            while i < len && !(chars[i].1 == '*' && chars.get(i + 1).map(|&(_, c)| c) == Some('/')) {
+                i += 1;
+            }
+            i = (i + 2).min(len);
+            spans.push(span(b, offset_at(&chars, source, i), TokenKind::Comment, None));
+            continue;
+        }
+
+        // String literals.
+        if c == '"' {
+            i += 1;
+            while i < len && chars[i].1 != '"' {
+                if chars[i].1 == '\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            i = (i + 1).min(len);
+            spans.push(span(b, offset_at(&chars, source, i), TokenKind::String, None));
+            continue;
+        }
+
+        // Numbers.
+        if c.is_ascii_digit() {
+            while i < len && chars[i].1.is_ascii_alphanumeric() {
+                i += 1;
+            }
+            // A single `.` followed by a digit extends the number as a
+            // fractional part. Anything else — `..` (range), `.method()`,
+            // a bare trailing `.` — belongs to the next token, not this one.
+            if chars.get(i).map(|&(_, c)| c) == Some('.')
+                && chars.get(i + 1).is_some_and(|&(_, c)| c.is_ascii_digit())
+            {
+                i += 1;
+                while i < len && chars[i].1.is_ascii_alphanumeric() {
+                    i += 1;
+                }
+            }
+            spans.push(span(b, offset_at(&chars, source, i), TokenKind::Number, None));
+            continue;
+        }
+
+        // Lifetimes: 'a, 'static, ...
+        if c == '\'' && chars.get(i + 1).is_some_and(|&(_, c)| c.is_alphabetic() || c == '_') {
+            i += 1;
+            let name_start = offset_at(&chars, source, i);
+            while i < len && (chars[i].1.is_alphanumeric() || chars[i].1 == '_') {
+                i += 1;
+            }
+            let end = offset_at(&chars, source, i);
+            let name = &source[name_start..end];
+            let color = if options.rainbow {
+                Some(rainbow.lifetime(name, b))
+            } else {
+                None
+            };
+            spans.push(span(b, end, TokenKind::Lifetime, color));
+            continue;
+        }
+
+        // Identifiers / keywords / types / macros.
+        if c.is_alphabetic() || c == '_' {
+            while i < len && (chars[i].1.is_alphanumeric() || chars[i].1 == '_') {
+                i += 1;
+            }
+            let word_end = offset_at(&chars, source, i);
+            let word = &source[b..word_end];
+            let is_macro = chars.get(i).map(|&(_, c)| c) == Some('!');
+            let end = if is_macro {
+                offset_at(&chars, source, i + 1)
+            } else {
+                word_end
+            };
+
+            if is_macro {
+                spans.push(span(b, end, TokenKind::Macro, None));
+            } else if word == "let" {
+                spans.push(span(b, end, TokenKind::Keyword, None));
+                expect_binding = true;
+            } else if KEYWORDS.contains(&word) {
+                spans.push(span(b, end, TokenKind::Keyword, None));
+            } else if PRIMITIVE_TYPES.contains(&word) || word.chars().next().is_some_and(|ch| ch.is_uppercase()) {
+                spans.push(span(b, end, TokenKind::Type, None));
+            } else if expect_binding && options.rainbow {
+                let color = rainbow.define(word, b);
+                spans.push(span(b, end, TokenKind::Variable, Some(color)));
+                expect_binding = false;
+            } else if options.rainbow {
+                let color = rainbow.lookup(word);
+                spans.push(span(b, end, TokenKind::Variable, color));
+            } else {
+                spans.push(span(b, end, TokenKind::Variable, None));
+            }
+
+            if word != "let" && word != "mut" {
+                expect_binding = false;
+            }
+            i = if is_macro { i + 1 } else { i };
+            continue;
+        }
+
+        // Closure parameters: |x, y| introduce bindings too.
+        if c == '|' && options.rainbow {
+            if let Some(close) = find_closure_param_end(&chars, i) {
+                highlight_closure_params(&chars, source, i + 1, close, &mut rainbow, &mut spans);
+                let close_byte = chars[close].0;
+                spans.push(span(b, b + 1, TokenKind::Punctuation, None));
+                spans.push(span(close_byte, close_byte + 1, TokenKind::Punctuation, None));
+                i = close + 1;
+                continue;
+            }
+        }
+
+        if c == '{' {
+            rainbow.push_scope();
+            spans.push(span(b, b + 1, TokenKind::Punctuation, None));
+            i += 1;
+            continue;
+        }
+        if c == '}' {
+            rainbow.pop_scope();
+            spans.push(span(b, b + 1, TokenKind::Punctuation, None));
+            i += 1;
+            continue;
+        }
+
+        if OPERATORS.contains(c) {
+            spans.push(span(b, b + c.len_utf8(), TokenKind::Operator, None));
+            i += 1;
+            continue;
+        }
+
+        // Unrecognized char (punctuation not covered above); skip it.
+        i += 1;
+    }
+
+    spans
+}
+
+fn span(start: usize, end: usize, kind: TokenKind, color: Option<Color>) -> HighlightSpan {
+    HighlightSpan {
+        start,
+        end,
+        kind,
+        color,
+    }
+}
+
+/// Finds the char index of the closing `|` for a closure parameter list
+/// starting right after the opening `|` at char index `open`, or `None` if
+/// this isn't actually a closure (e.g. a bitwise-or operator).
+fn find_closure_param_end(chars: &[(usize, char)], open: usize) -> Option<usize> {
+    let mut i = open + 1;
+    while i < chars.len() {
+        match chars[i].1 {
+            '|' => return Some(i),
+            '\n' | ';' | '{' => return None,
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// Highlights the closure parameter list spanning char indices
+/// `[start..end)` (the `|`-delimited range found by
+/// [`find_closure_param_end`]) as rainbow-colored bindings.
+fn highlight_closure_params(
+    chars: &[(usize, char)],
+    source: &str,
+    start: usize,
+    end: usize,
+    rainbow: &mut RainbowState,
+    spans: &mut Vec<HighlightSpan>,
+) {
+    let mut i = start;
+    while i < end {
+        let c = chars[i].1;
+        if c == '&' || c.is_whitespace() || c == ',' {
+            i += 1;
+            continue;
+        }
+        if c.is_alphabetic() || c == '_' {
+            let name_start = chars[i].0;
+            while i < end && (chars[i].1.is_alphanumeric() || chars[i].1 == '_') {
+                i += 1;
+            }
+            let name_end = offset_at(chars, source, i);
+            let name = &source[name_start..name_end];
+            let color = rainbow.define(name, name_start);
+            spans.push(span(name_start, name_end, TokenKind::Variable, Some(color)));
+            // Skip an optional `: Type` annotation without highlighting it
+            // as a binding.
+            while i < end && chars[i].1 != ',' {
+                i += 1;
+            }
+            continue;
+        }
+        i += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variable_colors(source: &str, options: &HighlightOptions) -> Vec<Option<Color>> {
+        highlight(source, options)
+            .into_iter()
+            .filter(|s| s.kind == TokenKind::Variable && &source[s.start..s.end] == "x")
+            .map(|s| s.color)
+            .collect()
+    }
+
+    #[test]
+    fn rainbow_reuses_hue_for_same_binding_within_scope() {
+        let options = HighlightOptions { rainbow: true };
+        let colors = variable_colors("fn f() { let x = 1; x + x }", &options);
+        assert_eq!(colors.len(), 3);
+        assert!(colors.iter().all(Option::is_some));
+        assert!(colors.windows(2).all(|w| w[0] == w[1]));
+    }
+
+    #[test]
+    fn rainbow_off_leaves_variables_uncolored() {
+        let options = HighlightOptions { rainbow: false };
+        let colors = variable_colors("fn f() { let x = 1; x }", &options);
+        assert!(colors.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn shadowed_binding_gets_a_different_hue_per_scope() {
+        let options = HighlightOptions { rainbow: true };
+        let colors = variable_colors("fn f() { let x = 1; { let x = 2; x; } x }", &options);
+        let [outer_def, inner_def, inner_use, outer_use]: [Option<Color>; 4] =
+            colors.try_into().expect("four `x` references");
+        assert_eq!(inner_def, inner_use, "use resolves to the innermost scope's binding");
+        assert_eq!(outer_def, outer_use, "use after the block resolves back to the outer binding");
+        assert_ne!(outer_def, inner_def, "shadowing gets a distinct hue, not the outer one");
+    }
+
+    #[test]
+    fn identifier_scanning_does_not_panic_on_multibyte_utf8() {
+        // A naive `bytes[i] as char` scan would stop mid-codepoint and
+        // slice `source` on a non-char boundary; this must not panic.
+        let source = "fn café() {}";
+        let spans = highlight(source, &HighlightOptions::default());
+        let ident = spans
+            .iter()
+            .find(|s| s.kind == TokenKind::Variable)
+            .expect("café lexed as an identifier");
+        assert_eq!(&source[ident.start..ident.end], "café");
+    }
+}
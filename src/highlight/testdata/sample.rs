@@ -0,0 +1,13 @@
+struct Foo<T> {
+    x: i32,
+    y: T,
+}
+
+fn compute(v: Foo<i32>) -> i32 {
+    let Foo { x: z, y } = v;
+    unsafe {
+        static mut COUNTER: i32 = 0;
+        COUNTER += z + y;
+    }
+    z
+}
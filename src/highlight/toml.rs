@@ -0,0 +1,145 @@
+//! Minimal TOML tokenizer, registered as a built-in [`super::registry::Language`]
+//! alongside Rust to prove the grammar registry is genuinely pluggable.
+//!
+//! Like `rust.rs`, this walks `source.char_indices()` rather than raw
+//! bytes so multi-byte UTF-8 characters (e.g. in a quoted string or a bare
+//! key) can't split a span on a non-char boundary.
+
+use super::{HighlightSpan, TokenKind};
+
+pub(crate) const KEYWORDS: &[&str] = &["true", "false"];
+pub(crate) const OPERATORS: &str = "=.,[]{}";
+
+pub fn highlight(source: &str) -> Vec<HighlightSpan> {
+    let chars: Vec<(usize, char)> = source.char_indices().collect();
+    let len = chars.len();
+    let mut spans = Vec::new();
+    let mut i = 0;
+    let mut at_line_start = true;
+
+    while i < len {
+        let (b, c) = chars[i];
+
+        if c == '\n' {
+            at_line_start = true;
+            i += 1;
+            continue;
+        }
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        if c == '#' {
+            while i < len && chars[i].1 != '\n' {
+                i += 1;
+            }
+            spans.push(span(b, offset_at(&chars, source, i), TokenKind::Comment));
+            at_line_start = false;
+            continue;
+        }
+
+        // `[section]` / `[[array.of.tables]]` headers.
+        if c == '[' && at_line_start {
+            let array_of_tables = chars.get(i + 1).map(|&(_, c)| c) == Some('[');
+            while i < len && chars[i].1 != ']' && chars[i].1 != '\n' {
+                i += 1;
+            }
+            i = (i + 1).min(len);
+            if array_of_tables && chars.get(i).map(|&(_, c)| c) == Some(']') {
+                i += 1;
+            }
+            spans.push(span(b, offset_at(&chars, source, i), TokenKind::Struct));
+            at_line_start = false;
+            continue;
+        }
+
+        if c == '"' || c == '\'' {
+            let quote = c;
+            i += 1;
+            while i < len && chars[i].1 != quote {
+                if chars[i].1 == '\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            i = (i + 1).min(len);
+            spans.push(span(b, offset_at(&chars, source, i), TokenKind::String));
+            at_line_start = false;
+            continue;
+        }
+
+        if c.is_ascii_digit() || (c == '-' && chars.get(i + 1).is_some_and(|&(_, c)| c.is_ascii_digit())) {
+            while i < len
+                && (chars[i].1.is_ascii_alphanumeric() || chars[i].1 == '.' || chars[i].1 == '-' || chars[i].1 == ':')
+            {
+                i += 1;
+            }
+            spans.push(span(b, offset_at(&chars, source, i), TokenKind::Number));
+            at_line_start = false;
+            continue;
+        }
+
+        if c.is_alphabetic() || c == '_' {
+            while i < len && (chars[i].1.is_alphanumeric() || chars[i].1 == '_' || chars[i].1 == '-') {
+                i += 1;
+            }
+            let end = offset_at(&chars, source, i);
+            let word = &source[b..end];
+            let kind = if KEYWORDS.contains(&word) {
+                TokenKind::Keyword
+            } else {
+                // A bare key at the start of an assignment; TOML has no
+                // separate "field vs binding" distinction worth modeling,
+                // so key names simply map to `Field`.
+                TokenKind::Field
+            };
+            spans.push(span(b, end, kind));
+            at_line_start = false;
+            continue;
+        }
+
+        if OPERATORS.contains(c) {
+            spans.push(span(b, b + c.len_utf8(), TokenKind::Operator));
+            i += 1;
+            at_line_start = false;
+            continue;
+        }
+
+        i += 1;
+        at_line_start = false;
+    }
+
+    spans
+}
+
+/// Byte offset at which char index `idx` starts, or `source.len()` once
+/// `idx` has run off the end — i.e. the right place to close a span that
+/// ended at `idx`.
+fn offset_at(chars: &[(usize, char)], source: &str, idx: usize) -> usize {
+    chars.get(idx).map(|&(o, _)| o).unwrap_or(source.len())
+}
+
+fn span(start: usize, end: usize, kind: TokenKind) -> HighlightSpan {
+    HighlightSpan {
+        start,
+        end,
+        kind,
+        color: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_scanning_does_not_panic_on_multibyte_utf8() {
+        // A naive `bytes[i] as char` scan would stop mid-codepoint and
+        // slice `source` on a non-char boundary; this must not panic.
+        let source = "café = \"espresso\"";
+        let spans = highlight(source);
+        let key = spans.iter().find(|s| s.kind == TokenKind::Field).expect("café lexed as a key");
+        assert_eq!(&source[key.start..key.end], "café");
+    }
+}